@@ -0,0 +1,162 @@
+use crate::AbsRect;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Mirrors Taffy's small fixed-size measure cache: a handful of entries is enough to absorb the
+// repeated restages that dominate in practice (e.g. a resize that only perturbs the root area),
+// without paying for a full hashmap per node.
+const CACHE_SIZE: usize = 4;
+
+#[derive(Clone)]
+struct Entry<T> {
+    constraint: AbsRect,
+    result: T,
+}
+
+/// Propagates a "something beneath me changed" bit up through ancestor caches. Each node's
+/// [`Cache`] owns one `DirtyFlag`; mutating a node's props calls [`DirtyFlag::mark`] on it, which
+/// also marks the flag it was itself derived from via [`DirtyFlag::child`] - and so on up the
+/// chain - so a mutation anywhere in a subtree invalidates every cache between it and the root,
+/// not just the node that changed.
+#[derive(Clone)]
+pub struct DirtyFlag {
+    bit: Rc<Cell<bool>>,
+    parent: Option<Box<DirtyFlag>>,
+}
+
+impl Default for DirtyFlag {
+    fn default() -> Self {
+        Self {
+            bit: Rc::new(Cell::new(true)),
+            parent: None,
+        }
+    }
+}
+
+impl DirtyFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derives a flag for a child node. Marking the child flag also marks `self`, so dirtiness
+    /// bubbles upward however many ancestors the caller chained `child()` through.
+    pub fn child(&self) -> Self {
+        Self {
+            bit: Rc::new(Cell::new(true)),
+            parent: Some(Box::new(self.clone())),
+        }
+    }
+
+    /// Marks this node (and, transitively, every ancestor flag it descends from) dirty.
+    pub fn mark(&self) {
+        self.bit.set(true);
+        if let Some(parent) = &self.parent {
+            parent.mark();
+        }
+    }
+
+    /// Reads and clears the bit, returning whether it was set.
+    fn take(&self) -> bool {
+        self.bit.replace(false)
+    }
+}
+
+/// Per-node memoization of a `Desc::stage` result, keyed on the imposed `AbsRect`. Entries
+/// survive until `dirty` is marked - by this node's own state changing, or by a descendant's
+/// `DirtyFlag::mark()` propagating up to it - at which point the next `get` drops them all.
+#[derive(Clone)]
+pub struct Cache<T> {
+    entries: Vec<Entry<T>>,
+    dirty: DirtyFlag,
+}
+
+impl<T> Cache<T> {
+    pub fn new(dirty: DirtyFlag) -> Self {
+        Self {
+            entries: Vec::with_capacity(CACHE_SIZE),
+            dirty,
+        }
+    }
+
+    /// The flag this cache watches. Descendants that want their own prop mutations to
+    /// invalidate this cache should be given `dirty_flag().child()`.
+    pub fn dirty_flag(&self) -> &DirtyFlag {
+        &self.dirty
+    }
+}
+
+impl<T: Clone> Cache<T> {
+    /// Returns a clone of the cached result for `constraint`, if the dirty flag hasn't been
+    /// marked since the last call and a matching entry is present. Checking the flag here (and
+    /// clearing stale entries on a miss) is what makes a descendant's `mark()` actually evict.
+    pub fn get(&mut self, constraint: AbsRect) -> Option<T> {
+        if self.dirty.take() {
+            self.entries.clear();
+            return None;
+        }
+        self.entries
+            .iter()
+            .find(|e| e.constraint == constraint)
+            .map(|e| e.result.clone())
+    }
+
+    /// Records a freshly computed result for `constraint`, evicting the oldest entry once the
+    /// cache is full.
+    pub fn store(&mut self, constraint: AbsRect, result: T) {
+        if self.entries.len() >= CACHE_SIZE {
+            self.entries.remove(0);
+        }
+        self.entries.push(Entry { constraint, result });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mark_propagates_to_every_ancestor() {
+        let grandparent = DirtyFlag::new();
+        let parent = grandparent.child();
+        let child = parent.child();
+
+        // Fresh flags start dirty; clear all three before the real assertion.
+        grandparent.take();
+        parent.take();
+        child.take();
+
+        child.mark();
+
+        assert!(parent.take());
+        assert!(grandparent.take());
+    }
+
+    #[test]
+    fn marking_a_child_does_not_dirty_an_unrelated_sibling() {
+        let parent = DirtyFlag::new();
+        let a = parent.child();
+        let b = parent.child();
+        parent.take();
+        a.take();
+        b.take();
+
+        a.mark();
+
+        assert!(!b.take());
+    }
+
+    #[test]
+    fn get_evicts_all_entries_once_dirtied() {
+        let dirty = DirtyFlag::new();
+        let mut cache: Cache<u32> = Cache::new(dirty.clone());
+
+        // A fresh cache starts dirty, so the first lookup is always a miss.
+        assert_eq!(cache.get(AbsRect::ZERO), None);
+        cache.store(AbsRect::ZERO, 42);
+        assert_eq!(cache.get(AbsRect::ZERO), Some(42));
+
+        dirty.mark();
+
+        assert_eq!(cache.get(AbsRect::ZERO), None);
+    }
+}
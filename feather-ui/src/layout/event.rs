@@ -0,0 +1,196 @@
+use super::Staged;
+// `RTree::new`, `insert`, and `locate_at_point` are assumed to have the shapes used below;
+// `rtree` is defined outside this module, so building against it requires that shape to already
+// exist there.
+use crate::rtree;
+
+/// Identifies a node within one staged tree, in paint order: node 0 was staged (and so painted)
+/// first, meaning later indices sit on top of it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A pointer event bubbling through the staged tree. `point` is the location it occurred at;
+/// `stopped` lets a handler stop further propagation, mirroring `Event.stopPropagation()` in the
+/// DOM.
+pub struct PointerEvent {
+    pub point: (f32, f32),
+    stopped: bool,
+}
+
+impl PointerEvent {
+    pub fn new(point: (f32, f32)) -> Self {
+        Self {
+            point,
+            stopped: false,
+        }
+    }
+
+    pub fn stop_propagation(&mut self) {
+        self.stopped = true;
+    }
+}
+
+/// Which leg of dispatch a handler is being invoked for: capture runs root-to-target, bubble runs
+/// target-to-root, exactly like DOM event phases.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Capture,
+    Bubble,
+}
+
+/// Indexes a staged tree's absolute node rects in an R-tree so pointer events can be routed
+/// without re-walking the whole tree per query. Built fresh after every restage, since the
+/// staged geometry it indexes is itself immutable once produced.
+pub struct EventTree {
+    tree: rtree::RTree<NodeId>,
+    // Every indexed node in paint order, as returned by `nodes`. `hit_test` sorts by the ids
+    // themselves, and `dispatch` builds its capture/bubble path from `parents`.
+    order: Vec<NodeId>,
+    parents: Vec<Option<NodeId>>,
+}
+
+impl EventTree {
+    /// Indexes a staged tree into an R-tree so pointer events can be routed without re-walking
+    /// the tree per query. Walks `root` via `Staged::children()` itself, in paint order (parent
+    /// before its children), so this can be driven directly off real `stage()` output instead of
+    /// a caller-flattened list.
+    pub fn build<AppData>(root: &dyn Staged<AppData>) -> Self {
+        let mut tree = rtree::RTree::new();
+        let mut order = Vec::new();
+        let mut parents = Vec::new();
+
+        Self::visit(root, None, &mut tree, &mut order, &mut parents);
+
+        Self {
+            tree,
+            order,
+            parents,
+        }
+    }
+
+    fn visit<AppData>(
+        node: &dyn Staged<AppData>,
+        parent: Option<NodeId>,
+        tree: &mut rtree::RTree<NodeId>,
+        order: &mut Vec<NodeId>,
+        parents: &mut Vec<Option<NodeId>>,
+    ) {
+        let id = NodeId(order.len());
+        order.push(id);
+        parents.push(parent);
+        tree.insert(node.get_area(), id);
+
+        for child in node.children() {
+            Self::visit(&**child, Some(id), tree, order, parents);
+        }
+    }
+
+    /// Every indexed node, in the paint order `build` assigned while walking the tree.
+    pub fn nodes(&self) -> &[NodeId] {
+        &self.order
+    }
+
+    /// Every node whose area contains `point`, ordered front-to-back (topmost/last-painted
+    /// first).
+    pub fn hit_test(&self, point: (f32, f32)) -> Vec<NodeId> {
+        let mut hits = self.tree.locate_at_point(point);
+        Self::sort_front_to_back(&mut hits);
+        hits
+    }
+
+    /// Orders hits topmost/last-painted first: since `NodeId` is assigned in paint order, that's
+    /// simply descending id.
+    fn sort_front_to_back(hits: &mut [NodeId]) {
+        hits.sort_by_key(|id| std::cmp::Reverse(id.0));
+    }
+
+    /// The path from `target` up to the root, via `parents`, in root-to-target order - i.e. the
+    /// order `dispatch`'s capture phase runs handlers in. Bubble runs the same path reversed.
+    fn capture_path(parents: &[Option<NodeId>], target: NodeId) -> Vec<NodeId> {
+        let mut path = vec![target];
+        let mut cur = target;
+        while let Some(parent) = parents[cur.0] {
+            path.push(parent);
+            cur = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Bubbles `event` along the hit path under `event.point`: capture phase runs root-to-target,
+    /// then bubble phase runs target-to-root. Either phase can call
+    /// `event.stop_propagation()` to halt the rest of dispatch.
+    pub fn dispatch(&self, event: &mut PointerEvent, mut handle: impl FnMut(NodeId, Phase, &mut PointerEvent)) {
+        let Some(&target) = self.hit_test(event.point).first() else {
+            return;
+        };
+
+        let path = Self::capture_path(&self.parents, target);
+
+        for &node in path.iter() {
+            handle(node, Phase::Capture, event);
+            if event.stopped {
+                return;
+            }
+        }
+        for &node in path.iter().rev() {
+            handle(node, Phase::Bubble, event);
+            if event.stopped {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_front_to_back_orders_by_descending_paint_order() {
+        let mut hits = vec![NodeId(0), NodeId(3), NodeId(1)];
+        EventTree::sort_front_to_back(&mut hits);
+        assert_eq!(hits, vec![NodeId(3), NodeId(1), NodeId(0)]);
+    }
+
+    #[test]
+    fn capture_path_runs_root_to_target() {
+        // 0 (root) -> 1 -> 2 (target)
+        let parents = vec![None, Some(NodeId(0)), Some(NodeId(1))];
+        let path = EventTree::capture_path(&parents, NodeId(2));
+        assert_eq!(path, vec![NodeId(0), NodeId(1), NodeId(2)]);
+    }
+
+    #[test]
+    fn capture_path_of_the_root_is_just_the_root() {
+        let parents = vec![None];
+        let path = EventTree::capture_path(&parents, NodeId(0));
+        assert_eq!(path, vec![NodeId(0)]);
+    }
+
+    #[test]
+    fn capture_path_gives_capture_order_and_its_reverse_gives_bubble_order() {
+        let parents = vec![None, Some(NodeId(0)), Some(NodeId(1))];
+        let path = EventTree::capture_path(&parents, NodeId(2));
+
+        let mut capture_order = Vec::new();
+        for &node in path.iter() {
+            capture_order.push(node);
+        }
+        let mut bubble_order = Vec::new();
+        for &node in path.iter().rev() {
+            bubble_order.push(node);
+        }
+
+        assert_eq!(capture_order, vec![NodeId(0), NodeId(1), NodeId(2)]);
+        assert_eq!(bubble_order, vec![NodeId(2), NodeId(1), NodeId(0)]);
+    }
+
+    #[test]
+    fn stop_propagation_sets_the_stopped_flag() {
+        let mut event = PointerEvent::new((0.0, 0.0));
+        assert!(!event.stopped);
+        event.stop_propagation();
+        assert!(event.stopped);
+    }
+}
@@ -0,0 +1,53 @@
+/// A property that flows down the tree the way CSS properties like `color` or `font-size` do: a
+/// node either declares its own value or falls back to whatever its nearest ancestor resolved to.
+pub trait Inheritable: Clone {
+    /// Resolves the value a child should see, given the parent's already-resolved value (`None`
+    /// if nothing above has set one yet) and the child's own local override, if any. The default
+    /// mirrors CSS's `inherit` keyword: a local override wins outright, otherwise the parent's
+    /// value passes through unchanged. Properties that need to compose instead of replace (e.g. a
+    /// relative font-size scale) override this.
+    fn merge(parent: Option<&Self>, local: Option<&Self>) -> Option<Self> {
+        match local {
+            Some(value) => Some(value.clone()),
+            None => parent.cloned(),
+        }
+    }
+}
+
+/// The inherited style payload for nodes that don't declare any inheritable properties of their
+/// own. This is `Inherited`'s default style parameter, so existing `Desc` impls that only care
+/// about `area` can keep writing `Inherited` without naming a style type.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoStyle;
+
+impl Inheritable for NoStyle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Scale(f32);
+    impl Inheritable for Scale {}
+
+    #[test]
+    fn local_override_wins_over_parent() {
+        assert_eq!(Scale::merge(Some(&Scale(1.0)), Some(&Scale(2.0))), Some(Scale(2.0)));
+    }
+
+    #[test]
+    fn falls_back_to_parent_without_a_local_override() {
+        assert_eq!(Scale::merge(Some(&Scale(1.0)), None), Some(Scale(1.0)));
+    }
+
+    #[test]
+    fn resolves_to_none_with_nothing_set_anywhere() {
+        assert_eq!(Scale::merge(None, None), None);
+    }
+
+    #[test]
+    fn local_override_is_used_even_with_no_parent() {
+        assert_eq!(Scale::merge(None, Some(&Scale(2.0))), Some(Scale(2.0)));
+    }
+}
@@ -0,0 +1,119 @@
+use super::cache::DirtyFlag;
+use super::root::Inherited;
+use super::Desc;
+use super::Layout;
+use super::Staged;
+use crate::AbsRect;
+use dyn_clone::DynClone;
+use std::rc::Rc;
+
+/// Whether an axis has a concrete imposed extent or is merely bounded by its content when a
+/// [`Measured`] node is asked to size itself along that axis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AvailableSpace {
+    Definite(f32),
+    MinContent,
+    MaxContent,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Size<T> {
+    pub width: T,
+    pub height: T,
+}
+
+/// A leaf node that resolves its own geometry from its content instead of purely from
+/// `URect`/`AbsRect` percentages. `measure` is handed the already-known extents (an axis the
+/// imposed rect pinned down) and the available space on the other axes, and returns the size the
+/// content wants. This is how text shaping or image aspect ratios get threaded into layout
+/// without the layout core knowing anything about text or images.
+//
+// Not `serde`-serializable: `measure` is a closure, not data, so there is no wire format for it -
+// an application restoring a saved layout is expected to rebuild its `Measured` nodes' callbacks
+// itself and only persist/restore the surrounding `URect`/`AbsRect` configuration.
+#[derive(Clone)]
+pub struct Measured {
+    pub measure: Rc<dyn Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>,
+    // Derived from the nearest ancestor `Root`'s dirty flag via `DirtyFlag::child` when this node
+    // was built, so `set_measure` below invalidates not just a future cache of our own but every
+    // ancestor cache between us and that root.
+    dirty: DirtyFlag,
+}
+
+impl Measured {
+    pub fn new(
+        parent_dirty: &DirtyFlag,
+        measure: Rc<dyn Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>,
+    ) -> Self {
+        Self {
+            measure,
+            dirty: parent_dirty.child(),
+        }
+    }
+
+    /// Replaces the measure callback and marks this node (and every ancestor cache derived from
+    /// it) dirty, since a cached staged result computed with the old callback is now stale.
+    pub fn set_measure(&mut self, measure: Rc<dyn Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>) {
+        self.measure = measure;
+        self.dirty.mark();
+    }
+}
+
+// A staged leaf has no children of its own to stage, so it only needs to remember the area it
+// was finally resolved to.
+#[derive(Clone)]
+struct StagedLeaf {
+    area: AbsRect,
+}
+
+impl<AppData> Staged<AppData> for StagedLeaf {
+    fn get_area(&self) -> AbsRect {
+        self.area
+    }
+}
+
+impl<AppData: 'static> Desc<AppData> for Measured {
+    type Props = Measured;
+    type Impose = Inherited;
+    type Children<A: DynClone + ?Sized> = ();
+
+    fn stage(
+        props: &Self::Props,
+        area: AbsRect,
+        // A measured leaf resolves its geometry purely from its own content and the imposed
+        // area; it has no style of its own and no children to pass inherited context to.
+        _: &Self::Impose,
+        _: &Self::Children<dyn Layout<Self::Impose, AppData>>,
+    ) -> Box<dyn Staged<AppData>> {
+        let known = Size {
+            width: area.width().is_finite().then(|| area.width()),
+            height: area.height().is_finite().then(|| area.height()),
+        };
+        let available = Size {
+            width: if area.width().is_finite() {
+                AvailableSpace::Definite(area.width())
+            } else {
+                AvailableSpace::MaxContent
+            },
+            height: if area.height().is_finite() {
+                AvailableSpace::Definite(area.height())
+            } else {
+                AvailableSpace::MaxContent
+            },
+        };
+
+        // Only ask the callback to fill in whatever the imposed rect left unconstrained; a
+        // dimension the parent already pinned down is left untouched, and if both already are
+        // there is nothing left for `measure` to contribute.
+        let resolved = if known.width.is_none() || known.height.is_none() {
+            let size = (props.measure)(known, available);
+            area.with_size(known.width.unwrap_or(size.width), known.height.unwrap_or(size.height))
+        } else {
+            area
+        };
+
+        Box::new(StagedLeaf { area: resolved })
+    }
+}
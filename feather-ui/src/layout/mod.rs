@@ -0,0 +1,77 @@
+pub mod cache;
+pub mod event;
+pub mod inherit;
+pub mod measured;
+pub mod portal;
+pub mod root;
+pub mod rootset;
+pub mod transparent;
+
+use crate::AbsRect;
+use dyn_clone::DynClone;
+
+// `root::Inherited` and `root::Root` gate their `Serialize`/`Deserialize` impls behind this
+// feature, but both require `AbsRect`/`URect` themselves to derive `Serialize`/`Deserialize`,
+// and neither type (defined outside `layout`) does yet. Rather than let the feature fail with a
+// wall of trait-bound errors pointing nowhere near the real cause, fail fast here: the feature is
+// inert until `AbsRect`/`URect` gain the matching derive upstream.
+#[cfg(feature = "serde")]
+compile_error!(
+    "the `serde` feature is inert: layout::root::Inherited/Root serialization requires AbsRect \
+     and URect to derive Serialize/Deserialize, and neither does yet - add those derives upstream \
+     before enabling this feature"
+);
+
+/// A node's description: its props, what it imposes on its children, and how to stage itself
+/// given an already-resolved absolute area and its children. `imposed` is this node's own
+/// resolved `Impose` - computed by its parent by merging the parent's resolved context with this
+/// node's declared local values - so a node can keep passing its *own* resolved context to its
+/// children exactly the way it was handed down, rather than every consumer re-walking ancestors.
+pub trait Desc<AppData> {
+    type Props;
+    type Impose;
+    type Children<A: DynClone + ?Sized>;
+
+    fn stage(
+        props: &Self::Props,
+        area: AbsRect,
+        imposed: &Self::Impose,
+        children: &Self::Children<dyn Layout<Self::Impose, AppData>>,
+    ) -> Box<dyn Staged<AppData>>;
+}
+
+/// A concrete, already-constructed node in the logical tree: something that knows what it
+/// imposes on its own children (so its parent can resolve an absolute area for it) and can stage
+/// itself once that area, and the parent's resolved inherited context, are known.
+pub trait Layout<Impose, AppData>: DynClone {
+    /// This node's own declared `Impose`: the area it proposes for itself, and any local
+    /// override of inheritable style, as set when the node was built. A parent reads this to
+    /// compute the resolved context it then hands to `stage`.
+    fn get_imposed(&self) -> &Impose;
+
+    /// Stages this node into `area` (already resolved to an absolute rect by the parent), given
+    /// `imposed`: the parent's resolved context for this node, merging inherited style with this
+    /// node's own local override.
+    fn stage(&self, area: AbsRect, imposed: &Impose) -> Box<dyn Staged<AppData>>;
+}
+
+dyn_clone::clone_trait_object!(<Impose, AppData> Layout<Impose, AppData>);
+
+/// The result of staging a node: its resolved absolute area, plus - for pass-through nodes like
+/// `Transparent` that contribute no box of their own - the staged children that should be
+/// spliced into the *parent's* child list in this node's place. Most `Staged` implementations
+/// have nothing to splice (their children, if any, are nested normally inside them), hence the
+/// empty default.
+pub trait Staged<AppData>: DynClone {
+    fn get_area(&self) -> AbsRect;
+
+    fn children(&self) -> &[Box<dyn Staged<AppData>>] {
+        &[]
+    }
+}
+
+dyn_clone::clone_trait_object!(<AppData> Staged<AppData>);
+
+/// Marker for a `Desc` whose `Props` is simply itself, i.e. it has no separate builder type.
+pub trait Concrete<AppData>: Desc<AppData, Props = Self> + Sized {}
+impl<AppData, T: Desc<AppData, Props = T>> Concrete<AppData> for T {}
@@ -0,0 +1,121 @@
+use super::root::Inherited;
+use super::rootset::WindowId;
+use super::Desc;
+use super::Layout;
+use super::Staged;
+use crate::AbsRect;
+use dyn_clone::DynClone;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Where a [`Portal`]'s child ends up staged. Reuses `RootSet`'s window id, since a portal's
+/// only destination is one of the roots a `RootSet` owns.
+pub type RootId = WindowId;
+
+/// Identifies a single [`Portal`] node, so its entry in the registry can be replaced in place
+/// rather than appended to every frame. Callers should give each `Portal` in the tree its own
+/// stable id, the same way `WindowId` is stable per window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortalId(pub u64);
+
+/// Holds the most recently staged child for every live portal, keyed by destination and then by
+/// the portal's own id. A `RootSet` owns one of these and hands out clones of the `Rc` to the
+/// `Portal` nodes staged under it.
+///
+/// Entries are *replaced*, not appended: a `Portal` whose ancestor `Root` cache-hits and so
+/// doesn't re-run `stage` this frame still has its last registration sitting here, so
+/// `RootSet::stage` keeps finding it instead of the portal's content vanishing the moment nothing
+/// upstream of it changed. The flip side is that an entry outlives the `Portal` that wrote it: if
+/// a `Portal` stops being part of the tree, its last child lingers here until [`Self::remove`] is
+/// called for its id - `RootSet`/application code that tears down a subtree containing a `Portal`
+/// is expected to call it, the same way `RootSet::remove` is expected when a window closes.
+#[derive(Clone)]
+pub struct PortalRegistry<AppData> {
+    current: Rc<RefCell<HashMap<RootId, HashMap<PortalId, Box<dyn Layout<Inherited, AppData>>>>>>,
+}
+
+impl<AppData> Default for PortalRegistry<AppData> {
+    fn default() -> Self {
+        Self {
+            current: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+}
+
+impl<AppData> PortalRegistry<AppData> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn set(&self, target: RootId, id: PortalId, child: Box<dyn Layout<Inherited, AppData>>) {
+        self.current.borrow_mut().entry(target).or_default().insert(id, child);
+    }
+
+    /// Drops a portal's registration, e.g. once the `Portal` that owns `id` is removed from the
+    /// tree. Without this, a portal that stops being staged would otherwise linger here forever.
+    pub fn remove(&self, target: RootId, id: PortalId) {
+        if let Some(by_id) = self.current.borrow_mut().get_mut(&target) {
+            by_id.remove(&id);
+        }
+    }
+
+    /// Every portal child currently registered for `target`, cloned so staging them doesn't
+    /// consume the registration - a portal that didn't restage this frame keeps showing its last
+    /// registered child next frame too.
+    pub fn children(&self, target: RootId) -> Vec<Box<dyn Layout<Inherited, AppData>>> {
+        self.current
+            .borrow()
+            .get(&target)
+            .map(|by_id| by_id.values().map(|child| dyn_clone::clone_box(&**child)).collect())
+            .unwrap_or_default()
+    }
+}
+
+// A portal contributes no area of its own at its original location; it is a placeholder that
+// keeps the logical tree (props, dirty propagation) intact while its actual content is staged
+// elsewhere.
+#[derive(Clone)]
+struct StagedPlaceholder;
+
+impl<AppData> Staged<AppData> for StagedPlaceholder {
+    fn get_area(&self) -> AbsRect {
+        AbsRect::ZERO
+    }
+}
+
+/// Re-parents its child into a different `Root`'s coordinate space instead of staging it inline
+/// under its parent, while still participating in the logical tree the child was declared in.
+/// This is how modals, tooltips, and cross-window overlays are expressed without a second layout
+/// tree: the `Portal` just hands its child off to the `RootSet` via `registry`, which collects
+/// everything targeting a given root when that root is next staged.
+//
+// Not `serde`-serializable: `registry` is a live handle into a `RootSet`, not configuration, so
+// there is nothing meaningful to persist beyond `target` - and restoring `target` alone still
+// needs a `RootSet` to re-register with, which plain `Deserialize` has no way to supply.
+#[derive(Clone)]
+pub struct Portal<AppData> {
+    pub id: PortalId,
+    pub target: RootId,
+    pub registry: PortalRegistry<AppData>,
+}
+
+impl<AppData: 'static> Desc<AppData> for Portal<AppData> {
+    type Props = Portal<AppData>;
+    type Impose = Inherited;
+    type Children<A: DynClone + ?Sized> = Box<dyn Layout<Self::Impose, AppData>>;
+
+    fn stage(
+        props: &Self::Props,
+        _: AbsRect,
+        // A portal's child isn't staged here at all - it's handed off whole to `registry` and
+        // staged later by whatever `RootSet` reads `target`, which resolves its own inherited
+        // context for it at that point. There is nothing for this `imposed` to feed into.
+        _: &Self::Impose,
+        child: &Self::Children<dyn Layout<Self::Impose, AppData>>,
+    ) -> Box<dyn Staged<AppData>> {
+        props.registry.set(props.target, props.id, dyn_clone::clone_box(&**child));
+        Box::new(StagedPlaceholder)
+    }
+}
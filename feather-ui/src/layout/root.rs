@@ -1,37 +1,204 @@
-use super::basic::Basic;
-use super::Concrete;
+use super::cache::{Cache, DirtyFlag};
+use super::inherit::{Inheritable, NoStyle};
 use super::Desc;
 use super::Layout;
 use super::Staged;
-use crate::rtree;
 use crate::AbsRect;
 use crate::URect;
 use dyn_clone::DynClone;
+use std::cell::RefCell;
 use std::rc::Rc;
 
+/// What a node imposes on its children: the rect it proposes for itself (resolved to an absolute
+/// area by the parent before `stage` is called), plus whatever inheritable style context `S`
+/// flows down from ancestors. `S` defaults to [`NoStyle`] so nodes that don't care about
+/// inherited style can keep writing `Inherited` with no type argument.
+///
+/// `style` is `None` until something in the tree sets it - either a [`Root`]'s `base_style` at
+/// the bottom of the chain, or a node's own local override - so a subtree with nothing declaring
+/// style anywhere above it resolves to `None` rather than some arbitrary default.
+///
+/// The gated derive below requires `URect: Serialize + Deserialize`; `URect` is defined outside
+/// `feather-ui`'s layout module, so it needs the matching gated derive wherever it lives for the
+/// `serde` feature to actually build.
 #[derive(Clone)]
-pub struct Inherited {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Inherited<S: Inheritable = NoStyle> {
     area: URect,
+    style: Option<S>,
+}
+
+impl<S: Inheritable> Inherited<S> {
+    /// A node's own declared context: `area` is the rect it proposes for itself, with no local
+    /// style override, so it falls back to whatever its ancestors resolve.
+    pub fn new(area: URect) -> Self {
+        Self { area, style: None }
+    }
+
+    /// A node's own declared context with an explicit local style override.
+    pub fn with_style(area: URect, style: S) -> Self {
+        Self {
+            area,
+            style: Some(style),
+        }
+    }
+
+    /// The context a [`Root`] seeds its tree with: it has no ancestor of its own, so `base_style`
+    /// is the fallback every descendant ultimately resolves to if nothing overrides it. `area` is
+    /// irrelevant here (it is always overwritten by the first [`Inherited::for_child`] call) and
+    /// left at its default.
+    pub fn root(base_style: S) -> Self {
+        Self {
+            area: URect::default(),
+            style: Some(base_style),
+        }
+    }
+
+    pub fn area(&self) -> URect {
+        self.area
+    }
+
+    pub fn style(&self) -> Option<&S> {
+        self.style.as_ref()
+    }
+
+    /// Computes the `Inherited` a child should receive: its own declared `area`, plus the
+    /// inherited style resolved from this node's style and the child's local override. Each node
+    /// calls this once per child while staging, instead of every consumer re-walking ancestors to
+    /// fetch inherited values.
+    pub fn for_child(&self, area: URect, local_style: Option<&S>) -> Self {
+        Self {
+            area,
+            style: S::merge(self.style.as_ref(), local_style),
+        }
+    }
+}
+
+impl<S: Inheritable> Default for Inherited<S> {
+    /// Nothing imposed from outside: no area and no style. Used where a `Desc::stage` needs an
+    /// `imposed` argument but, like [`Root`], ignores it because it has no ancestor to inherit
+    /// from.
+    fn default() -> Self {
+        Self {
+            area: URect::default(),
+            style: None,
+        }
+    }
 }
 
 // The root node represents some area on the screen that contains a feather layout. Later this will turn
 // into an absolute bounding volume. There can be multiple root nodes, each mapping to a different window.
 #[derive(Clone)]
-pub struct Root {
+pub struct Root<AppData, S: Inheritable = NoStyle> {
     area: AbsRect,
+    // The inherited style every node in this root's tree ultimately falls back to, since a root
+    // has no ancestor of its own to inherit from.
+    base_style: S,
+    // Memoizes the staged output of `child` by imposed `AbsRect`, so a restage that imposes the
+    // same area (e.g. an unrelated sibling window resizing) can skip the child subtree entirely.
+    // Relies on `Staged` implementing `DynClone` so cache hits are a cheap `Rc`-free clone.
+    cache: Rc<RefCell<Cache<Box<dyn Staged<AppData>>>>>,
 }
 
-impl<AppData: 'static> Desc<AppData> for Root {
-    type Props = Root;
-    type Impose = Inherited;
+impl<AppData, S: Inheritable + Default> Root<AppData, S> {
+    pub fn new(area: AbsRect) -> Self {
+        Self::with_style(area, S::default())
+    }
+}
+
+impl<AppData, S: Inheritable> Root<AppData, S> {
+    pub fn with_style(area: AbsRect, base_style: S) -> Self {
+        Self {
+            area,
+            base_style,
+            cache: Rc::new(RefCell::new(Cache::new(DirtyFlag::new()))),
+        }
+    }
+
+    /// Updates this root's area and marks the cache's dirty flag, since every cached result was
+    /// computed against the old area.
+    pub fn set_area(&mut self, area: AbsRect) {
+        self.area = area;
+        self.cache.borrow().dirty_flag().mark();
+    }
+
+    pub fn area(&self) -> AbsRect {
+        self.area
+    }
+
+    /// The style every node in this root's tree ultimately falls back to if nothing along the
+    /// way overrides it.
+    pub fn base_style(&self) -> &S {
+        &self.base_style
+    }
+
+    /// The flag backing this root's cache. Anything staged under this root should be constructed
+    /// with `dirty_flag().child()` so that mutating its own props also invalidates this root's
+    /// cache, instead of only being caught on the next area change.
+    pub fn dirty_flag(&self) -> DirtyFlag {
+        self.cache.borrow().dirty_flag().clone()
+    }
+}
+
+// `Root`'s cache is a runtime memoization detail, not configuration, so it is deliberately left
+// out of the serialized form: only `area` and `base_style` are persisted, and a restored `Root` starts with an
+// empty cache. `RootSchemaV1` is the stable wire format; a hypothetical `RootSchemaV2` would
+// gain a field here while `serialize`/`deserialize` keep translating to/from the current `Root`.
+//
+// Deriving here requires `AbsRect: Serialize + Deserialize`, same as `Inherited` above requires
+// it of `URect` - both types live outside this module, so enabling the `serde` feature is only
+// valid once their own definitions carry the matching gated derive.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RootSchemaV1<S> {
+    area: AbsRect,
+    base_style: S,
+}
+
+#[cfg(feature = "serde")]
+impl<AppData, S: Inheritable + serde::Serialize> serde::Serialize for Root<AppData, S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        RootSchemaV1 {
+            area: self.area,
+            base_style: self.base_style.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, AppData, S: Inheritable + serde::Deserialize<'de>> serde::Deserialize<'de> for Root<AppData, S> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let schema = RootSchemaV1::deserialize(deserializer)?;
+        Ok(Self::with_style(schema.area, schema.base_style))
+    }
+}
+
+impl<AppData: 'static, S: Inheritable + 'static> Desc<AppData> for Root<AppData, S> {
+    type Props = Root<AppData, S>;
+    type Impose = Inherited<S>;
     type Children<A: DynClone + ?Sized> = Box<dyn Layout<Self::Impose, AppData>>;
 
     fn stage(
         props: &Self::Props,
         _: AbsRect,
+        _: &Self::Impose,
         child: &Self::Children<dyn Layout<Self::Impose, AppData>>,
     ) -> Box<dyn Staged<AppData>> {
+        // A root has no ancestor of its own, so it seeds inheritance with `base_style` rather
+        // than the `imposed` handed down from above (there is nothing above a root - it is
+        // ignored here).
+        let declared = child.get_imposed();
+        let resolved = Inherited::root(props.base_style.clone()).for_child(declared.area(), declared.style());
+        let imposed = resolved.area() * props.area;
+
+        if let Some(cached) = props.cache.borrow_mut().get(imposed) {
+            return cached;
+        }
+
         // We bypass creating our own node here as our staging node would be redundant.
-        child.stage(child.get_imposed().area * props.area)
+        let result = child.stage(imposed, &resolved);
+        props.cache.borrow_mut().store(imposed, result.clone());
+        result
     }
 }
\ No newline at end of file
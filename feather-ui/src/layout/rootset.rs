@@ -0,0 +1,96 @@
+use super::portal::PortalRegistry;
+use super::root::{Inherited, Root};
+use super::Desc;
+use super::Layout;
+use super::Staged;
+use crate::AbsRect;
+use std::collections::HashMap;
+
+/// Opaque handle identifying the window or surface a `Root` is staged into. Embedders are free
+/// to pick whatever representation suits their windowing layer; feather-ui only ever uses it as
+/// a map key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowId(pub u64);
+
+struct Entry<AppData> {
+    root: Root<AppData>,
+    child: Box<dyn Layout<Inherited, AppData>>,
+}
+
+/// Owns every `Root` an embedder is driving, one per window. Each root is staged
+/// independently, so resizing or restaging one window never touches the others - the only
+/// thing they share is this set, plus the [`PortalRegistry`] that lets a `Portal` anywhere in
+/// the tree hand its child off to one of these roots.
+pub struct RootSet<AppData> {
+    roots: HashMap<WindowId, Entry<AppData>>,
+    portals: PortalRegistry<AppData>,
+}
+
+impl<AppData: 'static> Default for RootSet<AppData> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<AppData: 'static> RootSet<AppData> {
+    pub fn new() -> Self {
+        Self {
+            roots: HashMap::new(),
+            portals: PortalRegistry::new(),
+        }
+    }
+
+    /// Hands out the registry `Portal` nodes targeting this set should be constructed with.
+    pub fn portal_registry(&self) -> PortalRegistry<AppData> {
+        self.portals.clone()
+    }
+
+    pub fn insert(&mut self, window_id: WindowId, root: Root<AppData>, child: Box<dyn Layout<Inherited, AppData>>) {
+        self.roots.insert(window_id, Entry { root, child });
+    }
+
+    /// Updates a single root's area. This only marks that root's own cache dirty; every other
+    /// root in the set keeps whatever it had cached.
+    pub fn resize(&mut self, window_id: WindowId, area: AbsRect) {
+        if let Some(entry) = self.roots.get_mut(&window_id) {
+            entry.root.set_area(area);
+        }
+    }
+
+    /// Restages the named window and returns its own staged tree followed by every portal child
+    /// currently targeting it, staged into its coordinate space. Returns `None` if no root was
+    /// registered under that id.
+    ///
+    /// `PortalRegistry` hands back each portal's *most recently* registered child rather than a
+    /// one-shot queue, so a portal still shows up here even on a frame where its own subtree
+    /// wasn't re-walked (e.g. an ancestor `Root` cache-hit skipped calling its `Desc::stage`) -
+    /// it only disappears once its `Portal` node is actually torn down and
+    /// `PortalRegistry::remove` is called for it.
+    pub fn stage(&self, window_id: WindowId) -> Option<Vec<Box<dyn Staged<AppData>>>> {
+        let entry = self.roots.get(&window_id)?;
+        let mut staged = vec![<Root<AppData> as Desc<AppData>>::stage(
+            &entry.root,
+            entry.root.area(),
+            &Inherited::default(),
+            &entry.child,
+        )];
+
+        // A portal child never went through `entry.root`'s own `Desc::stage`, so it would
+        // otherwise skip inheritance entirely; resolve it against the target root's own context
+        // here, exactly as `entry.root`'s direct child would have been.
+        let root_ctx = Inherited::root(entry.root.base_style().clone());
+        for portal_child in self.portals.children(window_id) {
+            let declared = portal_child.get_imposed();
+            let resolved = root_ctx.for_child(declared.area(), declared.style());
+            let imposed = resolved.area() * entry.root.area();
+            staged.push(portal_child.stage(imposed, &resolved));
+        }
+
+        Some(staged)
+    }
+
+    pub fn remove(&mut self, window_id: WindowId) {
+        self.roots.remove(&window_id);
+    }
+}
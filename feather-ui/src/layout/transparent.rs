@@ -0,0 +1,75 @@
+use super::root::Inherited;
+use super::Desc;
+use super::Layout;
+use super::Staged;
+use crate::AbsRect;
+use dyn_clone::DynClone;
+
+// Analogous to bevy's GhostNode/ControlNode: contributes no box of its own, so its staged output
+// is just its children's staged outputs, unchanged, rather than a node wrapping them. A parent
+// collecting its children's staged results should splice `children()` in place of treating this
+// as a single child.
+struct StagedGroup<AppData> {
+    children: Vec<Box<dyn Staged<AppData>>>,
+}
+
+// Written by hand rather than derived: a derive would demand `AppData: Clone`, which `Staged`'s
+// `DynClone` bound does not need.
+impl<AppData> Clone for StagedGroup<AppData> {
+    fn clone(&self) -> Self {
+        Self {
+            children: self.children.clone(),
+        }
+    }
+}
+
+impl<AppData> Staged<AppData> for StagedGroup<AppData> {
+    fn get_area(&self) -> AbsRect {
+        // A transparent node occupies no area of its own; its children carry whatever area they
+        // were staged with.
+        AbsRect::ZERO
+    }
+
+    /// The staged children that should be spliced into the parent's own child list in place of
+    /// this node. Overriding the trait method (rather than an inherent one) is what lets a parent
+    /// holding only `&dyn Staged<AppData>` actually reach them to splice.
+    fn children(&self) -> &[Box<dyn Staged<AppData>>] {
+        &self.children
+    }
+}
+
+/// A structural node that is invisible to layout: reactivity/wrapper layers (conditionals,
+/// fragments, list adapters) can insert a `Transparent` into the tree to group children without
+/// perturbing the computed geometry, since each child's own declared rect is resolved directly
+/// against the parent-imposed rect, with no box of `Transparent`'s own in between, so the
+/// children end up staged as if they were direct children of `Transparent`'s own parent.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transparent;
+
+impl<AppData: 'static> Desc<AppData> for Transparent {
+    type Props = Transparent;
+    type Impose = Inherited;
+    type Children<A: DynClone + ?Sized> = Vec<Box<dyn Layout<Self::Impose, AppData>>>;
+
+    fn stage(
+        _: &Self::Props,
+        area: AbsRect,
+        imposed: &Self::Impose,
+        children: &Self::Children<dyn Layout<Self::Impose, AppData>>,
+    ) -> Box<dyn Staged<AppData>> {
+        // Every child resolves its own declared rect against exactly the rect that was imposed
+        // on `Transparent` itself; there is no intermediate box in between. Style still resolves
+        // normally through `imposed`, since being invisible to layout doesn't mean being invisible to inheritance.
+        let staged = children
+            .iter()
+            .map(|child| {
+                let declared = child.get_imposed();
+                let resolved = imposed.for_child(declared.area(), declared.style());
+                child.stage(declared.area() * area, &resolved)
+            })
+            .collect();
+
+        Box::new(StagedGroup { children: staged })
+    }
+}